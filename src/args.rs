@@ -0,0 +1,72 @@
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+pub struct MineArgs {
+    #[arg(
+        long,
+        short,
+        value_name = "CORES_COUNT",
+        help = "The number of cores to allocate to mining",
+        default_value = "1"
+    )]
+    pub cores: u64,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "The number of seconds before the deadline to stop mining and start submitting",
+        default_value = "5"
+    )]
+    pub buffer_time: u64,
+
+    #[arg(
+        long,
+        value_name = "BACKEND",
+        help = "The hashing backend to mine with: cpu or gpu",
+        default_value = "cpu"
+    )]
+    pub backend: String,
+
+    #[arg(
+        long,
+        help = "Print hash-rate and difficulty-histogram telemetry as a JSON line after each round"
+    )]
+    pub stats: bool,
+
+    #[arg(
+        long,
+        value_name = "MULTIPLIER",
+        help = "Multiplier applied to the estimated priority fee before submission",
+        default_value = "1.0"
+    )]
+    pub priority_fee_multiplier: f64,
+
+    #[arg(
+        long,
+        value_name = "PERCENTILE",
+        help = "Percentile (0-100) of non-zero recent prioritization fees to use as the estimate",
+        default_value_t = crate::priority_fee::DEFAULT_PRIORITY_FEE_PERCENTILE,
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    pub priority_fee_percentile: u8,
+
+    #[arg(
+        long,
+        value_name = "MICROLAMPORTS",
+        help = "The maximum priority fee to pay for a transaction"
+    )]
+    pub max_priority_fee: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Randomize the priority fee uniformly between 0 and --max-priority-fee, to A/B test landing rates"
+    )]
+    pub randomize_priority_fee: bool,
+
+    #[arg(
+        long,
+        value_name = "WS_URL",
+        help = "Websocket RPC URL to subscribe to proof/clock/bus updates on, instead of polling"
+    )]
+    pub rpc_ws_url: Option<String>,
+}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use drillx::Hash;
+
+/// One dispatch of work handed to a `HashBackend`: hash `batch_size` nonces
+/// starting at `nonce_start` against `challenge`.
+pub struct HashBatch {
+    pub challenge: [u8; 32],
+    pub nonce_start: u64,
+    pub batch_size: u64,
+}
+
+/// Pluggable hashing backend selected via `--backend cpu|gpu`. The per-core
+/// scalar path (`CpuBackend`) and a batched backend for large nonce ranges
+/// (`GpuBackend`) both implement this, so `find_hash_par`
+/// keeps its global-best-difficulty reduction and cutoff logic unchanged
+/// while feeding far larger nonce strides per dispatch to `GpuBackend`.
+#[async_trait]
+pub trait HashBackend: Send + Sync {
+    /// Hashes the batch and returns the best `(nonce, difficulty, hash)`
+    /// found within it.
+    async fn hash_batch(&self, batch: HashBatch) -> Result<(u64, u32, Hash), String>;
+}
+
+/// Scalar backend: hashes one nonce at a time with `drillx::hash_with_memory`,
+/// matching the existing per-core loop in `find_hash_par`.
+pub struct CpuBackend;
+
+#[async_trait]
+impl HashBackend for CpuBackend {
+    async fn hash_batch(&self, batch: HashBatch) -> Result<(u64, u32, Hash), String> {
+        let mut memory = drillx::equix::SolverMemory::new();
+        let mut best_nonce = batch.nonce_start;
+        let mut best_difficulty = 0u32;
+        let mut best_hash = Hash::default();
+
+        for offset in 0..batch.batch_size {
+            let nonce = batch.nonce_start.wrapping_add(offset);
+            if let Ok(hx) =
+                drillx::hash_with_memory(&mut memory, &batch.challenge, &nonce.to_le_bytes())
+            {
+                let difficulty = hx.difficulty();
+                if difficulty > best_difficulty {
+                    best_nonce = nonce;
+                    best_difficulty = difficulty;
+                    best_hash = hx;
+                }
+            }
+        }
+
+        Ok((best_nonce, best_difficulty, best_hash))
+    }
+}
+
+/// Batched backend for `--backend gpu`: takes large nonce ranges through the
+/// same `HashBatch` protocol as `CpuBackend`.
+///
+/// No solver is dispatched in this build: `hash_batch` hashes no nonces and
+/// returns an error for every batch, naming the configured `solver_path`.
+pub struct GpuBackend {
+    pub solver_path: String,
+}
+
+#[async_trait]
+impl HashBackend for GpuBackend {
+    async fn hash_batch(&self, _batch: HashBatch) -> Result<(u64, u32, Hash), String> {
+        Err(format!(
+            "GPU backend not available in this build (configured solver: {})",
+            self.solver_path
+        ))
+    }
+}
+
+/// Builds the configured backend from the `--backend` flag.
+pub fn backend_from_name(name: &str, gpu_solver_path: Option<String>) -> Box<dyn HashBackend> {
+    match name {
+        "gpu" => Box::new(GpuBackend {
+            solver_path: gpu_solver_path.unwrap_or_else(|| "gpu-solver".to_string()),
+        }),
+        _ => Box::new(CpuBackend),
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use serde::Serialize;
+
+/// Tracks total hashes attempted and a difficulty histogram across all cores
+/// for a single mining round, for the live `mine` loop's telemetry. Attempts
+/// recorded with `record_batch` all count under their batch's best
+/// difficulty, so the histogram is not a per-hash distribution like the
+/// standalone benchmark's.
+pub struct HashRate {
+    total_hashes: AtomicU64,
+    difficulty_histogram: std::sync::Mutex<HashMap<u32, u64>>,
+    started_at: Instant,
+}
+
+/// JSON line emitted after each round when `--stats` is set.
+#[derive(Serialize)]
+pub struct HashRateStats {
+    pub elapsed_secs: u64,
+    pub total_hashes: u64,
+    pub hashes_per_sec: u64,
+    pub difficulty_histogram: HashMap<u32, u64>,
+}
+
+impl HashRate {
+    pub fn new() -> Self {
+        Self {
+            total_hashes: AtomicU64::new(0),
+            difficulty_histogram: std::sync::Mutex::new(HashMap::new()),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Records one more hash attempt at the given difficulty.
+    pub fn record(&self, difficulty: u32) {
+        self.record_batch(difficulty, 1);
+    }
+
+    /// Records a batch of `count` hash attempts that all produced the given
+    /// best difficulty. Used by batched backends (e.g. GPU) that only
+    /// surface the best result per dispatch rather than every hash.
+    pub fn record_batch(&self, difficulty: u32, count: u64) {
+        self.total_hashes.fetch_add(count, Ordering::Relaxed);
+        let mut histogram = self.difficulty_histogram.lock().unwrap();
+        *histogram.entry(difficulty).or_insert(0) += count;
+    }
+
+    /// Moving-average hashes/sec since this `HashRate` was created.
+    pub fn hashes_per_sec(&self) -> u64 {
+        let elapsed = self.started_at.elapsed().as_secs().max(1);
+        self.total_hashes.load(Ordering::Relaxed) / elapsed
+    }
+
+    /// Expected maximum difficulty reachable in `seconds_left`, given the
+    /// measured hash rate: difficulty `d` costs ~2^d hashes, so the expected
+    /// max `d` is `log2(hashes_per_sec * seconds_left)`.
+    pub fn expected_difficulty(&self, seconds_left: u64) -> u32 {
+        let budget = self.hashes_per_sec().saturating_mul(seconds_left.max(1));
+        if budget == 0 {
+            return 0;
+        }
+        (budget as f64).log2().floor().max(0.0) as u32
+    }
+
+    pub fn stats(&self) -> HashRateStats {
+        HashRateStats {
+            elapsed_secs: self.started_at.elapsed().as_secs(),
+            total_hashes: self.total_hashes.load(Ordering::Relaxed),
+            hashes_per_sec: self.hashes_per_sec(),
+            difficulty_histogram: self.difficulty_histogram.lock().unwrap().clone(),
+        }
+    }
+}
+
+impl Default for HashRate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,11 @@
-use std::{sync::Arc, sync::RwLock, time::Instant};
-use colored::*;
-use drillx::{
-    equix::{self},
-    Hash, Solution,
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    sync::Arc,
+    sync::RwLock,
+    time::Instant,
 };
+use colored::*;
+use drillx::{Hash, Solution};
 use ore_api::{
     consts::{BUS_ADDRESSES, BUS_COUNT, EPOCH_DURATION},
     state::{Bus, Config, Proof},
@@ -12,11 +14,16 @@ use ore_utils::AccountDeserialize;
 use rand::Rng;
 use solana_program::pubkey::Pubkey;
 use solana_rpc_client::spinner;
-use solana_sdk::signer::Signer;
+use solana_sdk::{clock::Clock, signer::Signer};
 use tokio::task;
 
 use crate::{
     args::MineArgs,
+    hash_backend::{backend_from_name, HashBackend, HashBatch},
+    hash_rate::HashRate,
+    priority_fee::PriorityFeeStrategy,
+    pubsub::{BusSubscription, ClockSubscription, ProofSubscription},
+    rpc::{with_retries, with_retries_catching_panics},
     send_and_confirm::ComputeBudget,
     utils::{
         amount_u64_to_string, get_clock, get_config, get_updated_proof_with_authority, proof_pubkey,
@@ -24,6 +31,13 @@ use crate::{
     Miner,
 };
 
+/// How long to wait for a pushed update before falling back to the unary RPC/polling path.
+const SUBSCRIPTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Margin (in difficulty levels) required above `min_difficulty` before
+/// `find_hash_par` will stop early to save power.
+const EARLY_STOP_DIFFICULTY_MARGIN: u32 = 2;
+
 impl Miner {
     pub async fn mine(&self, args: MineArgs) {
         // Open account, if needed.
@@ -33,15 +47,71 @@ impl Miner {
         // Check num threads
         self.check_num_cores(args.cores);
 
+        // GpuBackend is a protocol-only stub with no solver wired up yet, so it errors on every
+        // batch; reject --backend gpu here instead of letting find_hash_gpu return None almost
+        // instantly every round and spin the mine loop in an unthrottled busy loop of RPC calls.
+        if args.backend == "gpu" {
+            println!(
+                "{} --backend gpu is not implemented in this build (no solver configured); use --backend cpu",
+                "ERROR".bold().red()
+            );
+            return;
+        }
+
+        // Open persistent websocket subscriptions once, if configured, rather than reconnecting
+        // every mine-loop iteration. `mine()` then drives its loop off these pushed updates,
+        // falling back to the existing polling path whenever one isn't connected or times out.
+        let mut proof_subscription = match &args.rpc_ws_url {
+            Some(ws_url) => ProofSubscription::connect(ws_url, proof_pubkey(signer.pubkey())).await,
+            None => None,
+        };
+        let bus_subscription = match &args.rpc_ws_url {
+            Some(ws_url) => BusSubscription::connect(ws_url, &BUS_ADDRESSES).await,
+            None => None,
+        };
+        let clock_subscription = match &args.rpc_ws_url {
+            Some(ws_url) => ClockSubscription::connect(ws_url).await,
+            None => None,
+        };
+
         // Start mining loop
         let mut last_hash_at = 0;
         let mut last_balance = 0;
         loop {
-            // Fetch proof
-            let config = get_config(&self.rpc_client).await;
-            let proof =
-                get_updated_proof_with_authority(&self.rpc_client, signer.pubkey(), last_hash_at)
-                    .await;
+            // Fetch proof. Prefer the pushed update from the websocket subscription over
+            // re-polling, since it reacts the instant last_hash_at/challenge changes; fall
+            // back to the unary RPC fetch if the subscription isn't configured, times out, or
+            // only redelivers the stale snapshot `accountSubscribe` sends on open. Both unary
+            // fetches retry transient RPC failures the same way `find_bus` does before giving up.
+            let config = with_retries_catching_panics(|| get_config(&self.rpc_client))
+                .await
+                .expect("Failed to fetch config after retries");
+            let proof = match proof_subscription.as_mut() {
+                Some(subscription) => {
+                    match tokio::time::timeout(
+                        SUBSCRIPTION_TIMEOUT,
+                        subscription.next_changed_proof(last_hash_at),
+                    )
+                    .await
+                    {
+                        Ok(Some(proof)) => proof,
+                        _ => with_retries_catching_panics(|| {
+                            get_updated_proof_with_authority(
+                                &self.rpc_client,
+                                signer.pubkey(),
+                                last_hash_at,
+                            )
+                        })
+                        .await
+                        .expect("Failed to fetch proof after retries"),
+                    }
+                }
+                None => with_retries_catching_panics(|| {
+                    get_updated_proof_with_authority(&self.rpc_client, signer.pubkey(), last_hash_at)
+                })
+                .await
+                .expect("Failed to fetch proof after retries"),
+            };
             println!(
                 "\n\nStake: {} ORE\n{}  Multiplier: {:12}x",
                 amount_u64_to_string(proof.balance),
@@ -59,31 +129,70 @@ impl Miner {
             last_balance = proof.balance;
 
             // Calculate cutoff time
-            let cutoff_time = self.get_cutoff(proof, args.buffer_time).await;
+            let cutoff_time = self
+                .get_cutoff(proof, args.buffer_time, clock_subscription.as_ref())
+                .await;
 
             // Run drillx
-            let solution =
-                Self::find_hash_par(proof, cutoff_time, args.cores, config.min_difficulty as u32)
-                    .await;
+            let Some((solution, hash_rate)) = Self::find_hash_par(
+                proof,
+                cutoff_time,
+                args.cores,
+                config.min_difficulty as u32,
+                &args.backend,
+            )
+            .await
+            else {
+                // No core (or the GPU backend) produced a hash above difficulty 0, so there's no
+                // valid solution to submit. Skip this round rather than sending a bogus mine ix.
+                println!(
+                    "{} No valid hash found this round, skipping submission",
+                    "WARNING".bold().yellow()
+                );
+                continue;
+            };
+            if args.stats {
+                if let Ok(line) = serde_json::to_string(&hash_rate.stats()) {
+                    println!("{}", line);
+                }
+            }
 
             // Build instruction set
             let mut ixs = vec![ore_api::instruction::auth(proof_pubkey(signer.pubkey()))];
-            let mut compute_budget = 500_000;
-            if self.should_reset(config).await && rand::thread_rng().gen_range(0..100).eq(&0) {
-                compute_budget += 100_000;
+            if self.should_reset(config, clock_subscription.as_ref()).await
+                && rand::thread_rng().gen_range(0..100).eq(&0)
+            {
                 ixs.push(ore_api::instruction::reset(signer.pubkey()));
             }
 
             // Build mine ix
+            let bus = self.find_bus(bus_subscription.as_ref()).await;
             ixs.push(ore_api::instruction::mine(
                 signer.pubkey(),
                 signer.pubkey(),
-                self.find_bus().await,
+                bus,
                 solution,
             ));
 
+            // Estimate a priority fee from recent fees paid on the accounts this tx touches,
+            // rather than landing with no compute-unit price during congestion. The compute-unit
+            // limit itself is measured by simulating `ixs` inside `send_and_confirm`, so we only
+            // ever hand it the price here and it's the sole place a compute-budget ix is added.
+            let priority_fee_strategy = PriorityFeeStrategy::new(
+                args.priority_fee_percentile,
+                args.priority_fee_multiplier,
+                args.max_priority_fee,
+                args.randomize_priority_fee,
+            );
+            let priority_fee = priority_fee_strategy
+                .estimate(
+                    &self.rpc_client,
+                    &[proof_pubkey(signer.pubkey()), bus, signer.pubkey()],
+                )
+                .await;
+
             // Submit transaction
-            self.send_and_confirm(&ixs, ComputeBudget::Fixed(compute_budget), false)
+            self.send_and_confirm(&ixs, ComputeBudget::Dynamic(priority_fee), false)
                 .await
                 .ok();
         }
@@ -94,73 +203,102 @@ impl Miner {
         cutoff_time: u64,
         cores: u64,
         min_difficulty: u32,
-    ) -> Solution {
-        // Dispatch job to each thread using tokio::task
+        backend: &str,
+    ) -> Option<(Solution, Arc<HashRate>)> {
+        if backend == "gpu" {
+            return Self::find_hash_gpu(proof, cutoff_time, min_difficulty).await;
+        }
+
+        // Dispatch job to each thread using tokio::task, driving every core's nonce range
+        // through the same `HashBackend` trait `find_hash_gpu` uses, just with `CpuBackend`'s
+        // scalar dispatch and a small batch size so the early-stop/cutoff checks stay as
+        // responsive as the old per-hash loop. `hash_batch` never actually awaits (there's no
+        // device I/O on the CPU path), so each blocking task drives it with `block_on` rather
+        // than giving up the dedicated thread per core that the tight hashing loop needs.
+        const CPU_BATCH_SIZE: u64 = 100;
+        let cpu_backend: Arc<dyn HashBackend> = Arc::from(backend_from_name("cpu", None));
         let progress_bar = Arc::new(spinner::new_progress_bar());
         let global_best_difficulty = Arc::new(RwLock::new(0u32));
+        let hash_rate = Arc::new(HashRate::new());
+        // Shared across all cores so that any one of them noticing the hash rate has cleared
+        // the early-stop margin stops every core, not just itself.
+        let should_stop_early = Arc::new(AtomicBool::new(false));
         progress_bar.set_message("Mining...");
 
         let handles: Vec<_> = (0..cores)
             .map(|i| {
+                let cpu_backend = Arc::clone(&cpu_backend);
                 let global_best_difficulty = Arc::clone(&global_best_difficulty);
+                let hash_rate = Arc::clone(&hash_rate);
+                let should_stop_early = Arc::clone(&should_stop_early);
                 let proof = proof.clone();
                 let progress_bar = progress_bar.clone();
-                let mut memory = equix::SolverMemory::new();
-                
+
                 task::spawn_blocking(move || -> Result<(u64, u32, Hash), String> {
                     // Start hashing
                     let timer = Instant::now();
-                    let mut nonce = u64::MAX.saturating_div(cores).saturating_mul(i);
-                    let mut best_nonce: u64 = nonce;
+                    let mut nonce_start = u64::MAX.saturating_div(cores).saturating_mul(i);
+                    let mut best_nonce: u64 = nonce_start;
                     let mut best_difficulty: u32 = 0;
                     let mut best_hash = Hash::default();
-                    
+
                     loop {
-                        // Create hash
-                        if let Ok(hx) = drillx::hash_with_memory(
-                            &mut memory,
-                            &proof.challenge,
-                            &nonce.to_le_bytes(),
-                        ) {
-                            let difficulty = hx.difficulty();
-                            if difficulty > best_difficulty {
-                                best_nonce = nonce;
-                                best_difficulty = difficulty;
-                                best_hash = hx;
-                                // Update global best difficulty
-                                if best_difficulty > *global_best_difficulty.read().unwrap() {
-                                    *global_best_difficulty.write().unwrap() = best_difficulty;
-                                }
+                        let batch = HashBatch {
+                            challenge: proof.challenge,
+                            nonce_start,
+                            batch_size: CPU_BATCH_SIZE,
+                        };
+                        let (nonce, difficulty, hx) =
+                            futures::executor::block_on(cpu_backend.hash_batch(batch))?;
+                        hash_rate.record_batch(difficulty, CPU_BATCH_SIZE);
+                        if difficulty > best_difficulty {
+                            best_nonce = nonce;
+                            best_difficulty = difficulty;
+                            best_hash = hx;
+                            // Update global best difficulty
+                            if best_difficulty > *global_best_difficulty.read().unwrap() {
+                                *global_best_difficulty.write().unwrap() = best_difficulty;
                             }
                         }
 
                         // Exit if time has elapsed
-                        if nonce % 100 == 0 {
-                            let global_best_difficulty = *global_best_difficulty.read().unwrap();
-                            if timer.elapsed().as_secs() >= cutoff_time {
-                                if i == 0 {
-                                    progress_bar.set_message(format!(
-                                        "Mining... (difficulty {})",
-                                        global_best_difficulty,
-                                    ));
-                                }
-                                if global_best_difficulty >= min_difficulty {
-                                    // Mine until min difficulty has been met
-                                    break;
-                                }
-                            } else if i == 0 {
+                        let global_best_difficulty = *global_best_difficulty.read().unwrap();
+                        let seconds_left = cutoff_time.saturating_sub(timer.elapsed().as_secs());
+                        if timer.elapsed().as_secs() >= cutoff_time {
+                            if i == 0 {
                                 progress_bar.set_message(format!(
-                                    "Mining... (difficulty {}, time {})",
+                                    "Mining... (difficulty {})",
                                     global_best_difficulty,
-                                    format_duration(
-                                        cutoff_time.saturating_sub(timer.elapsed().as_secs()) as u32
-                                    ),
                                 ));
                             }
+                            if global_best_difficulty >= min_difficulty {
+                                // Mine until min difficulty has been met
+                                break;
+                            }
+                        } else if i == 0 {
+                            progress_bar.set_message(format!(
+                                "Mining... (difficulty {}, time {})",
+                                global_best_difficulty,
+                                format_duration(seconds_left as u32),
+                            ));
                         }
 
-                        // Increment nonce
-                        nonce += 1;
+                        // If the measured hash rate already makes a higher difficulty than
+                        // min_difficulty likely in the time remaining, flag every core to
+                        // stop early to save power (not just this one).
+                        let expected_difficulty = hash_rate.expected_difficulty(seconds_left);
+                        if expected_difficulty
+                            >= min_difficulty.saturating_add(EARLY_STOP_DIFFICULTY_MARGIN)
+                            && global_best_difficulty >= min_difficulty
+                        {
+                            should_stop_early.store(true, Ordering::Relaxed);
+                        }
+                        if should_stop_early.load(Ordering::Relaxed) {
+                            break;
+                        }
+
+                        // Advance to the next batch of nonces
+                        nonce_start = nonce_start.wrapping_add(CPU_BATCH_SIZE);
                     }
 
                     // Return the best nonce
@@ -198,7 +336,97 @@ impl Miner {
             best_difficulty
         ));
 
-        Solution::new(best_hash.d, best_nonce.to_le_bytes())
+        // Warn if the measured hash rate wouldn't have reached min_difficulty
+        // within the full cutoff window, so the user knows to add more cores.
+        if hash_rate.expected_difficulty(cutoff_time) < min_difficulty {
+            println!(
+                "{} Hash rate of {} hashes/sec is too low to reliably reach difficulty {} within this epoch's timing. Consider adding more cores.",
+                "WARNING".bold().yellow(),
+                hash_rate.hashes_per_sec(),
+                min_difficulty
+            );
+        }
+
+        // A difficulty of 0 means every core failed to produce a single valid hash (e.g. all
+        // spawn_blocking tasks errored); refuse to hand mine() a worthless all-zero solution.
+        if best_difficulty == 0 {
+            return None;
+        }
+
+        Some((
+            Solution::new(best_hash.d, best_nonce.to_le_bytes()),
+            hash_rate,
+        ))
+    }
+
+    /// GPU-backed counterpart to the scalar loop above: dispatches large nonce
+    /// ranges to the configured `HashBackend` and awaits each batch's best
+    /// result, rather than hashing one nonce at a time. Keeps the same
+    /// global-best-difficulty reduction and cutoff logic as the CPU path.
+    ///
+    /// Returns `None` if the backend never produced a hash above difficulty 0 (e.g. every batch
+    /// errored), so the caller doesn't build and submit a mine ix for a bogus all-zero solution.
+    async fn find_hash_gpu(
+        proof: Proof,
+        cutoff_time: u64,
+        min_difficulty: u32,
+    ) -> Option<(Solution, Arc<HashRate>)> {
+        const GPU_BATCH_SIZE: u64 = 1_000_000;
+
+        let progress_bar = spinner::new_progress_bar();
+        let hash_rate = Arc::new(HashRate::new());
+        let backend = backend_from_name("gpu", None);
+
+        progress_bar.set_message("Mining (GPU)...");
+        let timer = Instant::now();
+        let mut nonce_start = 0u64;
+        let mut best_nonce = 0u64;
+        let mut best_difficulty = 0u32;
+        let mut best_hash = Hash::default();
+
+        while timer.elapsed().as_secs() < cutoff_time || best_difficulty < min_difficulty {
+            let batch = HashBatch {
+                challenge: proof.challenge,
+                nonce_start,
+                batch_size: GPU_BATCH_SIZE,
+            };
+            match backend.hash_batch(batch).await {
+                Ok((nonce, difficulty, hash)) => {
+                    hash_rate.record_batch(difficulty, GPU_BATCH_SIZE);
+                    if difficulty > best_difficulty {
+                        best_nonce = nonce;
+                        best_difficulty = difficulty;
+                        best_hash = hash;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error in GPU backend: {}", e);
+                    break;
+                }
+            }
+
+            nonce_start = nonce_start.wrapping_add(GPU_BATCH_SIZE);
+            progress_bar.set_message(format!(
+                "Mining (GPU)... (difficulty {}, time {})",
+                best_difficulty,
+                format_duration(cutoff_time.saturating_sub(timer.elapsed().as_secs()) as u32)
+            ));
+        }
+
+        progress_bar.finish_with_message(format!(
+            "Best hash: {} (difficulty {})",
+            bs58::encode(best_hash.h).into_string(),
+            best_difficulty
+        ));
+
+        if best_difficulty == 0 {
+            return None;
+        }
+
+        Some((
+            Solution::new(best_hash.d, best_nonce.to_le_bytes()),
+            hash_rate,
+        ))
     }
 
     pub fn check_num_cores(&self, cores: u64) {
@@ -212,8 +440,22 @@ impl Miner {
         }
     }
 
-    async fn should_reset(&self, config: Config) -> bool {
-        let clock = get_clock(&self.rpc_client).await;
+    /// Reads the clock sysvar, preferring the latest value pushed over `clock_subscription`
+    /// (when connected) over a fresh unary RPC call each time. Retries transient RPC failures
+    /// the same way `find_bus` does before giving up.
+    async fn read_clock(&self, clock_subscription: Option<&ClockSubscription>) -> Clock {
+        if let Some(subscription) = clock_subscription {
+            if let Some(clock) = subscription.latest() {
+                return clock;
+            }
+        }
+        with_retries_catching_panics(|| get_clock(&self.rpc_client))
+            .await
+            .expect("Failed to fetch clock after retries")
+    }
+
+    async fn should_reset(&self, config: Config, clock_subscription: Option<&ClockSubscription>) -> bool {
+        let clock = self.read_clock(clock_subscription).await;
         config
             .last_reset_at
             .saturating_add(EPOCH_DURATION)
@@ -221,8 +463,13 @@ impl Miner {
             .le(&clock.unix_timestamp)
     }
 
-    async fn get_cutoff(&self, proof: Proof, buffer_time: u64) -> u64 {
-        let clock = get_clock(&self.rpc_client).await;
+    async fn get_cutoff(
+        &self,
+        proof: Proof,
+        buffer_time: u64,
+        clock_subscription: Option<&ClockSubscription>,
+    ) -> u64 {
+        let clock = self.read_clock(clock_subscription).await;
         proof
             .last_hash_at
             .saturating_add(60)
@@ -231,9 +478,20 @@ impl Miner {
             .max(0) as u64
     }
 
-    async fn find_bus(&self) -> Pubkey {
-        // Fetch the bus with the largest balance
-        if let Ok(accounts) = self.rpc_client.get_multiple_accounts(&BUS_ADDRESSES).await {
+    async fn find_bus(&self, bus_subscription: Option<&BusSubscription>) -> Pubkey {
+        // Prefer the latest balances pushed over `bus_subscription` (when connected) over a
+        // fresh `get_multiple_accounts` call.
+        if let Some(subscription) = bus_subscription {
+            if let Some(bus) = subscription.top_bus(&BUS_ADDRESSES) {
+                return bus;
+            }
+        }
+
+        // Fetch the bus with the largest balance, retrying transient RPC failures before
+        // resorting to a random bus, since a random (possibly empty) bus can waste a
+        // landed transaction.
+        let accounts = with_retries(|| self.rpc_client.get_multiple_accounts(&BUS_ADDRESSES)).await;
+        if let Ok(accounts) = accounts {
             let mut top_bus_balance: u64 = 0;
             let mut top_bus = BUS_ADDRESSES[0];
             for account in accounts {
@@ -0,0 +1,75 @@
+use rand::Rng;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey;
+
+/// Maximum number of recent slots the RPC node is willing to return fees for.
+const MAX_RECENT_FEE_SLOTS: usize = 150;
+
+/// Default percentile of the non-zero recent fees used to estimate the next
+/// priority fee. The 75th percentile tends to land without overpaying during
+/// normal congestion.
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+
+/// Strategy used to turn `getRecentPrioritizationFees` samples into a
+/// `microLamports` price for `ComputeBudgetInstruction::set_compute_unit_price`.
+///
+/// Mirrors the `--priority-fee-multiplier` / `--max-priority-fee` CLI flags.
+pub struct PriorityFeeStrategy {
+    pub percentile: u8,
+    pub multiplier: f64,
+    pub max_priority_fee: Option<u64>,
+    pub randomize: bool,
+}
+
+impl PriorityFeeStrategy {
+    pub fn new(percentile: u8, multiplier: f64, max_priority_fee: Option<u64>, randomize: bool) -> Self {
+        Self {
+            percentile,
+            multiplier,
+            max_priority_fee,
+            randomize,
+        }
+    }
+
+    /// Estimates the `microLamports` compute-unit price to submit with, based
+    /// on recent prioritization fees paid for the given accounts.
+    pub async fn estimate(&self, rpc_client: &RpcClient, accounts: &[Pubkey]) -> u64 {
+        let fee = match rpc_client.get_recent_prioritization_fees(accounts).await {
+            Ok(samples) => self.percentile_fee(samples),
+            Err(_) => 0,
+        };
+
+        let fee = ((fee as f64) * self.multiplier).round() as u64;
+        let fee = match self.max_priority_fee {
+            Some(max) => fee.min(max),
+            None => fee,
+        };
+
+        if self.randomize {
+            let max = self.max_priority_fee.unwrap_or(fee).max(1);
+            return rand::thread_rng().gen_range(0..max);
+        }
+
+        fee
+    }
+
+    /// Takes the configured percentile of the non-zero fees over the most
+    /// recent slots.
+    fn percentile_fee(&self, samples: Vec<solana_client::rpc_response::RpcPrioritizationFee>) -> u64 {
+        let mut fees: Vec<u64> = samples
+            .iter()
+            .rev()
+            .take(MAX_RECENT_FEE_SLOTS)
+            .map(|s| s.prioritization_fee)
+            .filter(|fee| *fee > 0)
+            .collect();
+
+        if fees.is_empty() {
+            return 0;
+        }
+
+        fees.sort_unstable();
+        let index = ((fees.len() - 1) * self.percentile as usize) / 100;
+        fees[index.min(fees.len() - 1)]
+    }
+}
@@ -0,0 +1,147 @@
+use futures::StreamExt;
+use ore_api::state::{Bus, Proof};
+use ore_utils::AccountDeserialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{nonblocking::pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{clock::Clock, commitment_config::CommitmentConfig, sysvar};
+use tokio::sync::watch;
+
+fn account_subscribe_config() -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..RpcAccountInfoConfig::default()
+    }
+}
+
+/// Opens a persistent `accountSubscribe` to `pubkey` and spawns a background task that
+/// forwards every decoded update onto the returned `watch` channel, so callers can read pushed
+/// state without paying a fresh websocket handshake per poll. When the stream ends (the
+/// websocket dropped), the channel is reset to `None` so callers fall back to polling. Returns
+/// `None` if the connection or subscription could not be established.
+async fn subscribe_account<T, F>(
+    ws_url: &str,
+    pubkey: Pubkey,
+    decode: F,
+) -> Option<watch::Receiver<Option<T>>>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&[u8]) -> Option<T> + Send + 'static,
+{
+    let client = PubsubClient::new(ws_url).await.ok()?;
+    let (tx, rx) = watch::channel(None);
+    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        // The stream borrows the client, so subscribe here: this task owns the client (and its
+        // unsubscribe handle) for as long as it runs.
+        let Ok((mut stream, _unsubscribe)) = client
+            .account_subscribe(&pubkey, Some(account_subscribe_config()))
+            .await
+        else {
+            let _ = ready_tx.send(false);
+            return;
+        };
+        let _ = ready_tx.send(true);
+        while let Some(update) = stream.next().await {
+            if let Some(data) = update.value.data.decode() {
+                if let Some(value) = decode(&data) {
+                    if tx.send(Some(value)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        // Disconnected: clear the last pushed value so it isn't served as current.
+        let _ = tx.send(None);
+    });
+
+    ready_rx.await.ok()?.then_some(rx)
+}
+
+/// Persistent websocket subscription to the signer's proof PDA, opened once and reused for the
+/// whole mine loop instead of reconnecting every iteration.
+pub struct ProofSubscription {
+    rx: watch::Receiver<Option<Proof>>,
+}
+
+impl ProofSubscription {
+    pub async fn connect(ws_url: &str, proof_pubkey: Pubkey) -> Option<Self> {
+        let rx = subscribe_account(ws_url, proof_pubkey, |data| {
+            Proof::try_from_bytes(data).ok().copied()
+        })
+        .await?;
+        Some(Self { rx })
+    }
+
+    /// Waits for the next proof update whose `last_hash_at` differs from `last_hash_at`.
+    /// `accountSubscribe` delivers the current, possibly-stale account state immediately on
+    /// subscribe, so this keeps draining updates until the challenge has actually changed
+    /// rather than handing back the same round the caller already mined.
+    pub async fn next_changed_proof(&mut self, last_hash_at: i64) -> Option<Proof> {
+        loop {
+            self.rx.changed().await.ok()?;
+            if let Some(proof) = *self.rx.borrow() {
+                if proof.last_hash_at != last_hash_at {
+                    return Some(proof);
+                }
+            }
+        }
+    }
+}
+
+/// Persistent subscription to every bus account, used by `find_bus` so selecting the bus with
+/// the largest balance doesn't cost a fresh `get_multiple_accounts` call every round.
+pub struct BusSubscription {
+    rxs: Vec<watch::Receiver<Option<Bus>>>,
+}
+
+impl BusSubscription {
+    pub async fn connect(ws_url: &str, bus_addresses: &[Pubkey]) -> Option<Self> {
+        let mut rxs = Vec::with_capacity(bus_addresses.len());
+        for bus_pubkey in bus_addresses {
+            let rx = subscribe_account(ws_url, *bus_pubkey, |data| {
+                Bus::try_from_bytes(data).ok().copied()
+            })
+            .await?;
+            rxs.push(rx);
+        }
+        Some(Self { rxs })
+    }
+
+    /// Returns the bus with the largest balance among the latest pushed updates, or `None` if
+    /// no update has arrived for any bus yet (caller should fall back to a unary RPC read).
+    pub fn top_bus(&self, bus_addresses: &[Pubkey]) -> Option<Pubkey> {
+        let mut top_bus_balance = 0u64;
+        let mut top_bus = None;
+        for (rx, pubkey) in self.rxs.iter().zip(bus_addresses) {
+            if let Some(bus) = *rx.borrow() {
+                if bus.rewards > top_bus_balance {
+                    top_bus_balance = bus.rewards;
+                    top_bus = Some(*pubkey);
+                }
+            }
+        }
+        top_bus
+    }
+}
+
+/// Persistent subscription to the clock sysvar, used by `get_cutoff`/`should_reset` so real
+/// slot time is tracked from pushed updates instead of a unary `get_clock` RPC call each round.
+pub struct ClockSubscription {
+    rx: watch::Receiver<Option<Clock>>,
+}
+
+impl ClockSubscription {
+    pub async fn connect(ws_url: &str) -> Option<Self> {
+        let rx = subscribe_account(ws_url, sysvar::clock::id(), |data| {
+            bincode::deserialize::<Clock>(data).ok()
+        })
+        .await?;
+        Some(Self { rx })
+    }
+
+    pub fn latest(&self) -> Option<Clock> {
+        self.rx.borrow().clone()
+    }
+}
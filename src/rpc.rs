@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+use futures::FutureExt;
+use rand::Rng;
+
+/// Maximum number of attempts `with_retries` makes before giving up, matching
+/// the retry budget other Solana bench tools use for unary RPC reads.
+const MAX_RETRIES: usize = 5;
+
+/// Base delay for exponential backoff between retries.
+const BASE_BACKOFF_MS: u64 = 200;
+
+/// Error returned once `with_retries` has exhausted `MAX_RETRIES` attempts.
+#[derive(Debug)]
+pub struct RetriesExhausted {
+    pub attempts: usize,
+}
+
+impl std::fmt::Display for RetriesExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RPC call failed after {} attempts", self.attempts)
+    }
+}
+
+impl std::error::Error for RetriesExhausted {}
+
+/// Retries an RPC read with exponential backoff and jitter, returning a typed
+/// error instead of letting callers fall back to degraded behavior (e.g. a
+/// random bus) on the first transient failure.
+pub async fn with_retries<T, E, F, Fut>(mut f: F) -> Result<T, RetriesExhausted>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    for attempt in 0..MAX_RETRIES {
+        if let Ok(value) = f().await {
+            return Ok(value);
+        }
+
+        if attempt + 1 < MAX_RETRIES {
+            let backoff = BASE_BACKOFF_MS.saturating_mul(1 << attempt);
+            let jitter = rand::thread_rng().gen_range(0..BASE_BACKOFF_MS);
+            tokio::time::sleep(Duration::from_millis(backoff + jitter)).await;
+        }
+    }
+
+    Err(RetriesExhausted {
+        attempts: MAX_RETRIES,
+    })
+}
+
+/// Retries an RPC read that (unlike the rest of this module) panics/unwraps deep inside its own
+/// implementation on failure instead of returning a `Result` — `get_config`, `get_clock`, and
+/// `get_updated_proof_with_authority` in `utils.rs` are like this. Catches the panic at each
+/// await point via `catch_unwind` and feeds it into `with_retries` as just another transient
+/// failure, so a single flaky account fetch doesn't unwind all the way out of the mine loop.
+pub async fn with_retries_catching_panics<T, F, Fut>(mut f: F) -> Result<T, RetriesExhausted>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    with_retries(move || AssertUnwindSafe(f()).catch_unwind()).await
+}
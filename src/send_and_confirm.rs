@@ -0,0 +1,91 @@
+use colored::*;
+use solana_program::instruction::Instruction;
+use solana_rpc_client::spinner;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, signature::Signature, signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::Miner;
+
+/// Margin added on top of a simulated compute-unit count, since live
+/// execution can consume slightly more units than simulation against a
+/// possibly stale account snapshot.
+const CU_LIMIT_MARGIN_PCT: u64 = 20;
+
+/// Upper bound on the compute-unit limit we'll ever request, matching the
+/// Solana runtime's per-transaction cap.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+pub enum ComputeBudget {
+    /// Submit with a fixed compute-unit limit and no compute-unit price.
+    Fixed(u32),
+    /// Submit with a compute-unit limit measured by simulating the
+    /// transaction, and the given compute-unit price (in microLamports),
+    /// estimated ahead of time from recent prioritization fees.
+    Dynamic(u64),
+}
+
+impl Miner {
+    pub async fn send_and_confirm(
+        &self,
+        ixs: &[Instruction],
+        compute_budget: ComputeBudget,
+        skip_confirm: bool,
+    ) -> Result<Signature, anyhow::Error> {
+        let progress_bar = spinner::new_progress_bar();
+        let signer = self.signer();
+        let client = self.rpc_client.clone();
+
+        // Build the compute-budget instructions up front so we only ever add one
+        // `SetComputeUnitLimit` and one `SetComputeUnitPrice`, never both a measured one here
+        // and a hand-pushed one from the caller.
+        let mut final_ixs = Vec::with_capacity(ixs.len() + 2);
+        match compute_budget {
+            ComputeBudget::Fixed(units) => {
+                final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+            }
+            ComputeBudget::Dynamic(unit_price) => {
+                let units = self
+                    .simulate_compute_units(ixs)
+                    .await
+                    .unwrap_or(MAX_COMPUTE_UNIT_LIMIT);
+                final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+                final_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+            }
+        }
+        final_ixs.extend_from_slice(ixs);
+
+        progress_bar.set_message("Submitting transaction...");
+        let hash = client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &final_ixs,
+            Some(&signer.pubkey()),
+            &[&signer],
+            hash,
+        );
+
+        let sig = if skip_confirm {
+            client.send_transaction(&tx).await?
+        } else {
+            client.send_and_confirm_transaction(&tx).await?
+        };
+
+        progress_bar.finish_with_message(format!("{} {}", "OK".bold().green(), sig));
+        Ok(sig)
+    }
+
+    /// Measures the compute units `ixs` actually consume by simulating the
+    /// transaction, padded by `CU_LIMIT_MARGIN_PCT`, rather than requesting a
+    /// hard-coded limit that either wastes budget or under-provisions.
+    async fn simulate_compute_units(&self, ixs: &[Instruction]) -> Option<u32> {
+        let signer = self.signer();
+        let hash = self.rpc_client.get_latest_blockhash().await.ok()?;
+        let tx =
+            Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[&signer], hash);
+        let simulation = self.rpc_client.simulate_transaction(&tx).await.ok()?;
+        let units_consumed = simulation.value.units_consumed?;
+        let padded = units_consumed.saturating_mul(100 + CU_LIMIT_MARGIN_PCT) / 100;
+        Some((padded as u32).min(MAX_COMPUTE_UNIT_LIMIT))
+    }
+}